@@ -32,7 +32,11 @@ pub fn init(command: &InitCommand) -> StrResult<()> {
 
     // Parse the manifest.
     let manifest = parse_manifest(&package_path)?;
-    manifest.validate(&spec)?;
+    for warning in manifest.validate(&spec)? {
+        if warning.is_user_facing() {
+            crate::print_warning(&warning.to_string()).unwrap();
+        }
+    }
 
     // Ensure that it is indeed a template.
     let Some(template) = &manifest.template else {
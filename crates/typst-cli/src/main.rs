@@ -83,6 +83,18 @@ fn print_error(msg: &str) -> io::Result<()> {
     writeln!(output, ": {msg}")
 }
 
+/// Print an application-level warning (independent from a source file).
+fn print_warning(msg: &str) -> io::Result<()> {
+    let styles = term::Styles::default();
+
+    let mut output = terminal::out();
+    output.set_color(&styles.header_warning)?;
+    write!(output, "warning")?;
+
+    output.reset()?;
+    writeln!(output, ": {msg}")
+}
+
 #[cfg(not(feature = "self-update"))]
 mod update {
     use crate::args::UpdateCommand;
@@ -80,8 +80,8 @@ impl PackageStorage {
             self.download_index()?
                 .iter()
                 .filter(|package| package.name == spec.name)
-                .map(|package| package.version)
-                .max()
+                .map(|package| package.version.clone())
+                .max_by(PackageVersion::cmp_preferring_releases)
                 .ok_or_else(|| eco_format!("failed to find package {spec}"))
         } else {
             // For other namespaces, search locally. We only search in the data
@@ -95,7 +95,7 @@ impl PackageStorage {
                 .filter_map(|entry| entry.ok())
                 .map(|entry| entry.path())
                 .filter_map(|path| path.file_name()?.to_string_lossy().parse().ok())
-                .max()
+                .max_by(PackageVersion::cmp_preferring_releases)
                 .ok_or_else(|| eco_format!("please specify the desired version"))
         }
     }
@@ -1,4 +1,3 @@
-use std::cmp::Reverse;
 use std::collections::{BTreeSet, HashSet};
 
 use ecow::{eco_format, EcoString};
@@ -1098,10 +1097,16 @@ impl<'a> CompletionContext<'a> {
     /// Add completions for all available packages.
     fn package_completions(&mut self, all_versions: bool) {
         let mut packages: Vec<_> = self.world.packages().iter().collect();
-        packages.sort_by_key(|(spec, _)| {
-            (&spec.namespace, &spec.name, Reverse(spec.version))
-        });
-        if !all_versions {
+        if all_versions {
+            packages.sort_by(|(a, _), (b, _)| a.cmp(b));
+        } else {
+            // Put the latest release of each package first so that a
+            // pre-release is only suggested if a package has no release.
+            packages.sort_by(|(a, _), (b, _)| {
+                (&a.namespace, &a.name)
+                    .cmp(&(&b.namespace, &b.name))
+                    .then_with(|| b.version.cmp_preferring_releases(&a.version))
+            });
             packages.dedup_by_key(|(spec, _)| (&spec.namespace, &spec.name));
         }
         for (package, description) in packages {
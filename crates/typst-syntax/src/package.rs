@@ -1,5 +1,7 @@
 //! Package manifest parsing.
 
+use std::cmp::Ordering;
+use std::convert::Infallible;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::str::FromStr;
 
@@ -10,7 +12,7 @@ use unscanny::Scanner;
 use crate::is_ident;
 
 /// A parsed package manifest.
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PackageManifest {
     /// Details about the package itself.
     pub package: PackageInfo,
@@ -31,7 +33,7 @@ pub struct TemplateInfo {
 /// The `[package]` key in the manifest.
 ///
 /// More fields are specified, but they are not relevant to the compiler.
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PackageInfo {
     /// The name of the package within its namespace.
     pub name: EcoString,
@@ -39,13 +41,220 @@ pub struct PackageInfo {
     pub version: PackageVersion,
     /// The path of the entrypoint into the package.
     pub entrypoint: EcoString,
-    /// The minimum required compiler version for the package.
-    pub compiler: Option<VersionBound>,
+    /// The range of compiler versions the package is compatible with.
+    pub compiler: Option<VersionRange>,
+    /// The package's authors.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<Author>,
+    /// A short, single-line description of the package.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<EcoString>,
+    /// The package's license as an SPDX expression.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<EcoString>,
+    /// A URL to the package's source code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<EcoString>,
+    /// A URL to the package's website or documentation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<EcoString>,
+    /// Search keywords for the package.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<EcoString>,
+    /// The package's categories, drawn from [`KNOWN_CATEGORIES`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<EcoString>,
+    /// Glob patterns for files to bundle. If present, `exclude` is ignored.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<EcoString>,
+    /// Glob patterns for files to leave out of the bundle.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<EcoString>,
+    /// Whether this version of the package is deprecated.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    /// Why the package is deprecated and what to use instead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_message: Option<EcoString>,
+    /// The `[package.metadata]` table, which is reserved for third-party
+    /// tools. It is not interpreted by the compiler.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<toml::Value>,
+}
+
+/// The maximum length of a package description in characters.
+const MAX_DESCRIPTION_LEN: usize = 300;
+
+/// The maximum number of keywords a package may specify.
+const MAX_KEYWORDS: usize = 5;
+
+/// The maximum length of a single keyword in characters.
+const MAX_KEYWORD_LEN: usize = 20;
+
+/// The categories a package may be listed in.
+pub const KNOWN_CATEGORIES: &[&str] = &[
+    "components",
+    "visualization",
+    "model",
+    "layout",
+    "text",
+    "languages",
+    "scripting",
+    "integration",
+    "utility",
+    "fun",
+    "book",
+    "report",
+    "paper",
+    "thesis",
+    "poster",
+    "flyer",
+    "presentation",
+    "cv",
+    "office",
+];
+
+/// Whether the string is one of the [`KNOWN_CATEGORIES`].
+pub fn validate_category(s: &str) -> bool {
+    KNOWN_CATEGORIES.contains(&s)
+}
+
+impl PackageInfo {
+    /// The description, truncated at a word boundary so that it is at most
+    /// `max_len` characters long, including the trailing `…` that is added
+    /// if anything was cut off. Returns an empty string if the package has no
+    /// description.
+    pub fn short_description(&self, max_len: usize) -> EcoString {
+        let Some(description) = &self.description else { return EcoString::new() };
+        if max_len == 0 {
+            return EcoString::new();
+        }
+        if description.chars().count() <= max_len {
+            return description.clone();
+        }
+
+        let end = description
+            .char_indices()
+            .nth(max_len.saturating_sub(1))
+            .map_or(description.len(), |(i, _)| i);
+        let mut prefix = &description[..end];
+        if !description[end..].starts_with(char::is_whitespace) {
+            // Drop the partial word at the end, if there is a full one before.
+            if let Some((head, _)) = prefix.rsplit_once(char::is_whitespace) {
+                prefix = head;
+            }
+        }
+
+        let mut short = EcoString::from(prefix.trim_end());
+        short.push('…');
+        short
+    }
+
+    /// The metadata for the given tool, i.e. the `[package.metadata.<tool>]`
+    /// table.
+    pub fn metadata_for_tool(&self, tool: &str) -> Option<&toml::Value> {
+        self.metadata.as_ref()?.get(tool)
+    }
+
+    /// The explicit exclusive upper bound on the compiler version, as in
+    /// `compiler = ">= 0.12, < 1.0"`.
+    pub fn compiler_max(&self) -> Option<VersionBound> {
+        match self.compiler {
+            Some(VersionRange::Bounded { max, .. }) => max,
+            _ => None,
+        }
+    }
+
+    /// The host of the package's repository URL, e.g. `github.com`.
+    pub fn repository_host(&self) -> Option<&str> {
+        self.repository.as_deref().and_then(url_host)
+    }
+
+    /// Whether the file at the given path (relative to the package root)
+    /// should be bundled when publishing the package.
+    ///
+    /// Like in Cargo, the `include` patterns take precedence: If there are
+    /// any, only matching files are bundled. Otherwise, all files except
+    /// those matching an `exclude` pattern are bundled. Patterns support `?`,
+    /// `*` and `**` wildcards. Later patterns override earlier ones and a
+    /// leading `!` negates a pattern. A pattern without a slash matches a file
+    /// or directory name at any depth.
+    pub fn should_include(&self, path: &str) -> bool {
+        let path = path.trim_start_matches("./").trim_start_matches('/');
+        if !self.include.is_empty() {
+            match_patterns(&self.include, path).unwrap_or(false)
+        } else {
+            !match_patterns(&self.exclude, path).unwrap_or(false)
+        }
+    }
+}
+
+/// Finds the last pattern matching the path and returns whether it is a
+/// positive one. Returns `None` if no pattern matches.
+fn match_patterns(patterns: &[EcoString], path: &str) -> Option<bool> {
+    patterns
+        .iter()
+        .rev()
+        .find_map(|pattern| match pattern.strip_prefix('!') {
+            Some(negated) => match_path(negated, path).then_some(false),
+            None => match_path(pattern, path).then_some(true),
+        })
+}
+
+/// Whether a glob pattern matches the path or one of its parent directories.
+fn match_path(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_start_matches('/').trim_end_matches('/');
+    if !pattern.contains('/') {
+        return path.split('/').any(|component| match_glob(pattern, component));
+    }
+
+    path.match_indices('/')
+        .map(|(i, _)| &path[..i])
+        .chain([path])
+        .any(|prefix| match_glob(pattern, prefix))
+}
+
+/// Whether a glob pattern matches the full text. A `?` matches any character
+/// and a `*` any sequence of characters, except for slashes. A `**` matches
+/// any sequence of characters including slashes.
+fn match_glob(pattern: &str, text: &str) -> bool {
+    let mut positions = (0..=text.len()).filter(|&i| text.is_char_boundary(i));
+    if let Some(rest) = pattern.strip_prefix("**") {
+        match rest.strip_prefix('/') {
+            Some(rest) => positions
+                .filter(|&i| i == 0 || text[..i].ends_with('/'))
+                .any(|i| match_glob(rest, &text[i..])),
+            None => positions.any(|i| match_glob(rest, &text[i..])),
+        }
+    } else if let Some(rest) = pattern.strip_prefix('*') {
+        positions
+            .take_while(|&i| !text[..i].contains('/'))
+            .any(|i| match_glob(rest, &text[i..]))
+    } else {
+        let mut pattern_chars = pattern.chars();
+        let mut text_chars = text.chars();
+        match (pattern_chars.next(), text_chars.next()) {
+            (None, None) => true,
+            (Some('?'), Some(c)) if c != '/' => {
+                match_glob(pattern_chars.as_str(), text_chars.as_str())
+            }
+            (Some(p), Some(c)) if p == c => {
+                match_glob(pattern_chars.as_str(), text_chars.as_str())
+            }
+            _ => false,
+        }
+    }
 }
 
 impl PackageManifest {
     /// Ensure that this manifest is indeed for the specified package.
-    pub fn validate(&self, spec: &PackageSpec) -> Result<(), EcoString> {
+    ///
+    /// Problems that should not prevent the package from being used are
+    /// returned as warnings.
+    pub fn validate(
+        &self,
+        spec: &PackageSpec,
+    ) -> Result<Vec<ManifestWarning>, EcoString> {
         if self.package.name != spec.name {
             return Err(eco_format!(
                 "package manifest contains mismatched name `{}`",
@@ -60,20 +269,355 @@ impl PackageManifest {
             ));
         }
 
-        if let Some(required) = self.package.compiler {
+        if let Some(required) = &self.package.compiler {
+            // A pre-release of the compiler satisfies bounds on its release.
             let current = PackageVersion::compiler();
-            if !current.matches_ge(&required) {
-                return Err(eco_format!(
-                    "package requires typst {required} or newer \
-                     (current version is {current})"
-                ));
+            let release = PackageVersion { prerelease: None, ..current.clone() };
+            if !release.satisfies(required) {
+                return Err(match required {
+                    VersionRange::Ge(bound) => eco_format!(
+                        "package requires typst {bound} or newer \
+                         (current version is {current})"
+                    ),
+                    _ => eco_format!(
+                        "package requires typst {required} \
+                         (current version is {current})"
+                    ),
+                });
+            }
+        }
+
+        let mut warnings = vec![];
+        if let Some(description) = &self.package.description {
+            if description.contains(['\n', '\r']) {
+                warnings.push(ManifestWarning::MultilineDescription);
+            }
+
+            let len = description.chars().count();
+            if len > MAX_DESCRIPTION_LEN {
+                warnings.push(ManifestWarning::DescriptionTooLong(len));
+            }
+        }
+
+        if self.package.keywords.len() > MAX_KEYWORDS {
+            warnings.push(ManifestWarning::TooManyKeywords(self.package.keywords.len()));
+        }
+
+        for keyword in &self.package.keywords {
+            if keyword.chars().count() > MAX_KEYWORD_LEN {
+                warnings.push(ManifestWarning::KeywordTooLong(keyword.clone()));
+            }
+        }
+
+        for category in &self.package.categories {
+            if !validate_category(category) {
+                warnings.push(ManifestWarning::UnknownCategory(category.clone()));
             }
         }
 
+        for author in &self.package.authors {
+            if !author.is_valid() {
+                warnings.push(ManifestWarning::InvalidAuthor(author.clone()));
+            }
+        }
+
+        if let Some(license) = &self.package.license {
+            if !is_spdx_expression(license) {
+                warnings.push(ManifestWarning::InvalidLicense(license.clone()));
+            }
+        }
+
+        for (field, url) in [
+            ("repository", &self.package.repository),
+            ("homepage", &self.package.homepage),
+        ] {
+            if let Some(url) = url {
+                if !is_absolute_url(url) {
+                    warnings.push(ManifestWarning::InvalidUrl(field, url.clone()));
+                }
+            }
+        }
+
+        if self.template.is_some() && self.package.description.is_none() {
+            warnings.push(ManifestWarning::MissingDescription);
+        }
+
+        if self.package.deprecated {
+            warnings.push(ManifestWarning::Deprecated(DeprecationWarning {
+                spec: spec.clone(),
+                message: self.package.deprecation_message.clone(),
+            }));
+        }
+
+        Ok(warnings)
+    }
+}
+
+/// A non-fatal problem with a package manifest.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ManifestWarning {
+    /// The description contains newlines.
+    MultilineDescription,
+    /// The description is longer than allowed, with the given length.
+    DescriptionTooLong(usize),
+    /// There are more than the allowed number of keywords.
+    TooManyKeywords(usize),
+    /// A keyword is longer than allowed.
+    KeywordTooLong(EcoString),
+    /// A category is not one of the [`KNOWN_CATEGORIES`].
+    UnknownCategory(EcoString),
+    /// An author has an empty name or a malformed contact.
+    InvalidAuthor(Author),
+    /// The `license` is not a valid SPDX expression.
+    InvalidLicense(EcoString),
+    /// The given field does not contain an absolute URL.
+    InvalidUrl(&'static str, EcoString),
+    /// The package is a template, but has no description.
+    MissingDescription,
+    /// The package is deprecated.
+    Deprecated(DeprecationWarning),
+}
+
+impl ManifestWarning {
+    /// Whether this warning concerns users of the package rather than its
+    /// author, who is the only one able to fix the other warnings.
+    pub fn is_user_facing(&self) -> bool {
+        matches!(self, Self::Deprecated(_))
+    }
+}
+
+impl Display for ManifestWarning {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::MultilineDescription => {
+                f.write_str("package description should not contain newlines")
+            }
+            Self::DescriptionTooLong(len) => write!(
+                f,
+                "package description is {len} characters long \
+                 (at most {MAX_DESCRIPTION_LEN} are allowed)"
+            ),
+            Self::TooManyKeywords(count) => write!(
+                f,
+                "package manifest contains {count} keywords \
+                 (at most {MAX_KEYWORDS} are allowed)"
+            ),
+            Self::KeywordTooLong(keyword) => write!(
+                f,
+                "package manifest contains too long keyword `{keyword}` \
+                 (at most {MAX_KEYWORD_LEN} characters are allowed)"
+            ),
+            Self::UnknownCategory(category) => {
+                write!(f, "package manifest contains unknown category `{category}`")
+            }
+            Self::InvalidAuthor(author) => write!(
+                f,
+                "package manifest contains invalid author `{author}` \
+                 (expected an email address, `@handle` or URL as contact)"
+            ),
+            Self::InvalidLicense(license) => write!(
+                f,
+                "package manifest contains invalid SPDX license expression `{license}`"
+            ),
+            Self::InvalidUrl(field, url) => write!(
+                f,
+                "package manifest contains invalid {field} URL `{url}` \
+                 (must be an absolute URL)"
+            ),
+            Self::MissingDescription => {
+                f.write_str("template package should have a description")
+            }
+            Self::Deprecated(warning) => Display::fmt(warning, f),
+        }
+    }
+}
+
+/// A warning that a package is deprecated.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct DeprecationWarning {
+    /// The deprecated package.
+    pub spec: PackageSpec,
+    /// The author's explanation, if any.
+    pub message: Option<EcoString>,
+}
+
+impl Display for DeprecationWarning {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "package {} is deprecated", self.spec)?;
+        if let Some(message) = &self.message {
+            write!(f, ": {message}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An author of a package.
+///
+/// In the manifest, an author is either given as a string of the form
+/// `"Name <contact>"` (where the contact is optional) or as a table with
+/// `name` and `email` keys. The contact is kept as written. It is typically
+/// an email address, a `@handle` or a URL, but this is not enforced when
+/// parsing so that manifests written in other conventions still load.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct Author {
+    /// The author's name.
+    pub name: EcoString,
+    /// How to contact the author.
+    pub contact: Option<EcoString>,
+}
+
+impl Author {
+    /// The author's email address, if the contact is one.
+    pub fn email(&self) -> Option<&str> {
+        self.contact.as_deref().filter(|contact| is_email(contact))
+    }
+
+    /// Whether the author has a non-empty name and a contact (if any) that
+    /// is an email address, a `@handle` or an HTTP(S) URL.
+    pub fn is_valid(&self) -> bool {
+        !self.name.trim().is_empty()
+            && self.contact.as_deref().map_or(true, |contact| {
+                is_email(contact)
+                    || contact.strip_prefix('@').is_some_and(|handle| {
+                        !handle.is_empty() && !handle.contains(char::is_whitespace)
+                    })
+                    || (contact.starts_with("http://") || contact.starts_with("https://"))
+                        && is_absolute_url(contact)
+            })
+    }
+}
+
+impl FromStr for Author {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Ok(match s.strip_suffix('>').and_then(|rest| rest.split_once('<')) {
+            Some((name, contact)) => Self {
+                name: name.trim().into(),
+                contact: Some(contact.trim().into()),
+            },
+            None => Self { name: s.into(), contact: None },
+        })
+    }
+}
+
+impl Debug for Author {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for Author {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if let Some(contact) = &self.contact {
+            write!(f, " <{contact}>")?;
+        }
         Ok(())
     }
 }
 
+impl Serialize for Author {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Author {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Str(EcoString),
+            Table { name: EcoString, email: Option<EcoString> },
+        }
+
+        Ok(match Repr::deserialize(d)? {
+            Repr::Str(string) => match string.parse() {
+                Ok(author) => author,
+                Err(never) => match never {},
+            },
+            Repr::Table { name, email } => Self { name, contact: email },
+        })
+    }
+}
+
+/// Splits a URL into its scheme and the remainder after the colon.
+fn split_url_scheme(url: &str) -> Option<(&str, &str)> {
+    let (scheme, rest) = url.split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = chars.next()?.is_ascii_alphabetic()
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then_some((scheme, rest))
+}
+
+/// Extracts the host from a URL with an authority component.
+fn url_host(url: &str) -> Option<&str> {
+    let (_, rest) = split_url_scheme(url)?;
+    let authority = rest.strip_prefix("//")?;
+    let authority = authority.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?;
+    let host = host.split(':').next()?;
+    (!host.is_empty()).then_some(host)
+}
+
+/// Whether a string is an absolute URL, i.e. one with a scheme.
+fn is_absolute_url(url: &str) -> bool {
+    split_url_scheme(url)
+        .is_some_and(|(_, rest)| !rest.is_empty() && !rest.contains(char::is_whitespace))
+}
+
+/// Whether a string is a plausible email address of the form
+/// `local-part@domain`.
+fn is_email(s: &str) -> bool {
+    let Some((local, domain)) = s.split_once('@') else { return false };
+    let valid = |c: char| !c.is_whitespace() && !matches!(c, '@' | '<' | '>');
+    !local.is_empty()
+        && !domain.is_empty()
+        && local.chars().all(valid)
+        && domain.chars().all(valid)
+        && domain.split('.').all(|part| !part.is_empty())
+}
+
+/// Whether a string is a syntactically valid SPDX license expression like
+/// `MIT OR Apache-2.0`.
+fn is_spdx_expression(s: &str) -> bool {
+    let spaced = s.replace('(', " ( ").replace(')', " ) ");
+    let mut tokens = spaced.split_whitespace().peekable();
+    let mut depth = 0usize;
+    let mut expect_operand = true;
+
+    while let Some(token) = tokens.next() {
+        match token {
+            "(" if expect_operand => depth += 1,
+            ")" if !expect_operand && depth > 0 => depth -= 1,
+            "AND" | "OR" if !expect_operand => expect_operand = true,
+            _ if expect_operand && is_spdx_id(token) => {
+                expect_operand = false;
+                if tokens.next_if_eq(&"WITH").is_some()
+                    && !tokens.next().is_some_and(is_spdx_id)
+                {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+
+    !expect_operand && depth == 0
+}
+
+/// Whether a string is a valid SPDX license or exception identifier.
+fn is_spdx_id(s: &str) -> bool {
+    let id = s.strip_suffix('+').unwrap_or(s);
+    !id.is_empty()
+        && !matches!(id, "AND" | "OR" | "WITH")
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':'))
+}
+
 /// Identifies a package.
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct PackageSpec {
@@ -92,6 +636,26 @@ impl PackageSpec {
             name: self.name.clone(),
         }
     }
+
+    /// Parse a package specification that may be percent-encoded, like
+    /// `%40preview%2Fexample%3A0.1.0`.
+    pub fn from_url_encoded(s: &str) -> Result<Self, EcoString> {
+        percent_decode(s)?.parse()
+    }
+
+    /// Percent-encode the specification so that it can be embedded into a
+    /// URL. All bytes outside of the unreserved set of RFC 3986 are escaped.
+    pub fn to_url_encoded(&self) -> EcoString {
+        let mut encoded = EcoString::new();
+        for byte in self.to_string().bytes() {
+            if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+                encoded.push(char::from(byte));
+            } else {
+                encoded.push_str(&eco_format!("%{byte:02X}"));
+            }
+        }
+        encoded
+    }
 }
 
 impl FromStr for PackageSpec {
@@ -106,6 +670,21 @@ impl FromStr for PackageSpec {
     }
 }
 
+/// Orders by namespace and name and then by version, newest first.
+impl Ord for PackageSpec {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.namespace, &self.name)
+            .cmp(&(&other.namespace, &other.name))
+            .then_with(|| other.version.cmp(&self.version))
+    }
+}
+
+impl PartialOrd for PackageSpec {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Debug for PackageSpec {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         Display::fmt(self, f)
@@ -119,7 +698,7 @@ impl Display for PackageSpec {
 }
 
 /// Identifies a package, but not a specific version of it.
-#[derive(Clone, Eq, PartialEq, Hash)]
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct VersionlessPackageSpec {
     /// The namespace the package lives in.
     pub namespace: EcoString,
@@ -128,6 +707,17 @@ pub struct VersionlessPackageSpec {
 }
 
 impl VersionlessPackageSpec {
+    /// Create a specification from its namespace and name, checking that both
+    /// are valid identifiers.
+    pub fn from_bare(namespace: &str, name: &str) -> Result<Self, EcoString> {
+        if !is_ident(namespace) {
+            Err(eco_format!("`{namespace}` is not a valid package namespace"))?;
+        } else if !is_ident(name) {
+            Err(eco_format!("`{name}` is not a valid package name"))?;
+        }
+        Ok(Self { namespace: namespace.into(), name: name.into() })
+    }
+
     /// Fill in the `version` to get a complete [`PackageSpec`].
     pub fn at(self, version: PackageVersion) -> PackageSpec {
         PackageSpec {
@@ -152,6 +742,12 @@ impl FromStr for VersionlessPackageSpec {
     }
 }
 
+impl From<PackageSpec> for VersionlessPackageSpec {
+    fn from(spec: PackageSpec) -> Self {
+        Self { namespace: spec.namespace, name: spec.name }
+    }
+}
+
 impl Debug for VersionlessPackageSpec {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         Display::fmt(self, f)
@@ -164,6 +760,34 @@ impl Display for VersionlessPackageSpec {
     }
 }
 
+/// Decode `%XX` escape sequences in a string.
+fn percent_decode(s: &str) -> Result<String, EcoString> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(byte) = iter.next() {
+        if byte != b'%' {
+            bytes.push(byte);
+            continue;
+        }
+
+        let mut digit = || iter.next().and_then(|b| char::from(b).to_digit(16));
+        let (Some(hi), Some(lo)) = (digit(), digit()) else {
+            return Err("package specification contains invalid percent-encoding".into());
+        };
+
+        bytes.push((hi * 16 + lo) as u8);
+    }
+
+    String::from_utf8(bytes)
+        .map_err(|_| "package specification is not valid UTF-8 after decoding".into())
+}
+
+/// Sorts package specifications by namespace and name and then by version,
+/// newest first.
+pub fn sort_specs(specs: &mut [PackageSpec]) {
+    specs.sort();
+}
+
 fn parse_namespace<'s>(s: &mut Scanner<'s>) -> Result<&'s str, EcoString> {
     if !s.eat_if('@') {
         Err("package specification must start with '@'")?;
@@ -204,7 +828,10 @@ fn parse_version(s: &mut Scanner) -> Result<PackageVersion, EcoString> {
 }
 
 /// A package's version.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+///
+/// Versions are ordered according to semantic versioning, meaning that a
+/// pre-release version is lower than its release (`1.0.0-alpha < 1.0.0`).
+#[derive(Clone, Eq, PartialEq, Hash)]
 pub struct PackageVersion {
     /// The package's major version.
     pub major: u32,
@@ -212,8 +839,31 @@ pub struct PackageVersion {
     pub minor: u32,
     /// The package's patch version.
     pub patch: u32,
+    /// The package's pre-release identifiers, e.g. `alpha.1`.
+    pub prerelease: Option<EcoString>,
 }
 
+/// The version of the crate in which this macro is invoked, as given by
+/// Cargo's `CARGO_PKG_VERSION` at compile time.
+///
+/// A function could only read the version of this crate, which is why this
+/// is a macro: `env!` expands at the call site. Compilation fails if the
+/// variable is not set.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __package_version {
+    () => {
+        $crate::package::PackageVersion::from_cargo_pkg_version(::std::env!(
+            "CARGO_PKG_VERSION",
+            "`package_version!` must be used in a crate that is built by Cargo"
+        ))
+        .expect("`CARGO_PKG_VERSION` contains an invalid version")
+    };
+}
+
+#[doc(inline)]
+pub use crate::__package_version as package_version;
+
 impl PackageVersion {
     /// The current compiler version.
     pub fn compiler() -> Self {
@@ -221,15 +871,97 @@ impl PackageVersion {
             major: env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap(),
             minor: env!("CARGO_PKG_VERSION_MINOR").parse().unwrap(),
             patch: env!("CARGO_PKG_VERSION_PATCH").parse().unwrap(),
+            prerelease: Some(env!("CARGO_PKG_VERSION_PRE"))
+                .filter(|pre| !pre.is_empty())
+                .map(Into::into),
         }
     }
 
+    /// Parse a version in the format of Cargo's `CARGO_PKG_VERSION`. Build
+    /// metadata (like in `1.0.0+20240101`) is ignored.
+    ///
+    /// This is useful in build scripts, which receive the version at runtime.
+    /// To embed the version of the crate being compiled, use the
+    /// [`package_version!`] macro instead.
+    pub fn from_cargo_pkg_version(s: &str) -> Result<Self, EcoString> {
+        s.split_once('+').map_or(s, |(version, _)| version).parse()
+    }
+
+    /// The next major version, e.g. `2.0.0` for `1.4.2`.
+    ///
+    /// Like the other bump methods, this drops any pre-release identifiers and
+    /// returns `None` if the bumped component would overflow. A pre-release
+    /// whose lower components are zero bumps to the release it leads up to,
+    /// e.g. `2.0.0` for `2.0.0-rc.1`.
+    pub const fn bump_major(&self) -> Option<Self> {
+        if self.prerelease.is_some() && self.minor == 0 && self.patch == 0 {
+            return Some(Self::new(self.major, 0, 0));
+        }
+        match self.major.checked_add(1) {
+            Some(major) => Some(Self::new(major, 0, 0)),
+            None => None,
+        }
+    }
+
+    /// The next minor version, e.g. `1.5.0` for `1.4.2` or `1.3.0` for
+    /// `1.3.0-rc.1`.
+    pub const fn bump_minor(&self) -> Option<Self> {
+        if self.prerelease.is_some() && self.patch == 0 {
+            return Some(Self::new(self.major, self.minor, 0));
+        }
+        match self.minor.checked_add(1) {
+            Some(minor) => Some(Self::new(self.major, minor, 0)),
+            None => None,
+        }
+    }
+
+    /// The next patch version, e.g. `1.4.3` for `1.4.2` or `1.2.3` for
+    /// `1.2.3-rc.1`.
+    pub const fn bump_patch(&self) -> Option<Self> {
+        if self.prerelease.is_some() {
+            return Some(Self::new(self.major, self.minor, self.patch));
+        }
+        match self.patch.checked_add(1) {
+            Some(patch) => Some(Self::new(self.major, self.minor, patch)),
+            None => None,
+        }
+    }
+
+    /// The next version with breaking changes. For `0.x` versions, this bumps
+    /// the minor version and otherwise the major version.
+    pub const fn next_breaking(&self) -> Option<Self> {
+        if self.major > 0 {
+            self.bump_major()
+        } else {
+            self.bump_minor()
+        }
+    }
+
+    /// Compares versions such that any release is greater than any
+    /// pre-release and otherwise by the usual ordering.
+    ///
+    /// Picking the maximum with this yields the latest release and only falls
+    /// back to a pre-release if there is no release at all.
+    pub fn cmp_preferring_releases(&self, other: &Self) -> Ordering {
+        self.prerelease
+            .is_none()
+            .cmp(&other.prerelease.is_none())
+            .then_with(|| self.cmp(other))
+    }
+
+    /// A release version without pre-release identifiers.
+    const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch, prerelease: None }
+    }
+
     /// Performs an `==` match with the given version bound. Version elements
-    /// missing in the bound are ignored.
+    /// missing in the bound are ignored. A pre-release of the bound's lowest
+    /// version (with missing elements taken as zero) never matches.
     pub fn matches_eq(&self, bound: &VersionBound) -> bool {
         self.major == bound.major
             && bound.minor.map_or(true, |minor| self.minor == minor)
             && bound.patch.map_or(true, |patch| self.patch == patch)
+            && !self.is_prerelease_of(bound)
     }
 
     /// Performs a `>` match with the given version bound. The match only
@@ -252,20 +984,21 @@ impl PackageVersion {
 
     /// Performs a `<` match with the given version bound. The match only
     /// succeeds if some version element in the bound is actually less than that
-    /// of the version.
+    /// of the version. A pre-release is also less than the bound's lowest
+    /// version (with missing elements taken as zero) if it leads up to it.
     pub fn matches_lt(&self, bound: &VersionBound) -> bool {
         if self.major != bound.major {
             return self.major < bound.major;
         }
-        let Some(minor) = bound.minor else { return false };
+        let Some(minor) = bound.minor else { return self.is_prerelease_of(bound) };
         if self.minor != minor {
             return self.minor < minor;
         }
-        let Some(patch) = bound.patch else { return false };
+        let Some(patch) = bound.patch else { return self.is_prerelease_of(bound) };
         if self.patch != patch {
             return self.patch < patch;
         }
-        false
+        self.is_prerelease_of(bound)
     }
 
     /// Performs a `>=` match with the given versions. The match succeeds when
@@ -279,12 +1012,47 @@ impl PackageVersion {
     pub fn matches_le(&self, bound: &VersionBound) -> bool {
         self.matches_eq(bound) || self.matches_lt(bound)
     }
+
+    /// Whether this is a pre-release of the bound's lowest version, where
+    /// missing elements are taken as zero.
+    fn is_prerelease_of(&self, bound: &VersionBound) -> bool {
+        self.prerelease.is_some()
+            && self.major == bound.major
+            && self.minor == bound.minor.unwrap_or(0)
+            && self.patch == bound.patch.unwrap_or(0)
+    }
+
+    /// Whether the version lies within the given range.
+    pub fn satisfies(&self, range: &VersionRange) -> bool {
+        match range {
+            VersionRange::Caret(bound) => {
+                let fixed = VersionBound { minor: None, patch: None, ..*bound };
+                self.matches_ge(bound) && self.matches_eq(&fixed)
+            }
+            VersionRange::Tilde(bound) => {
+                let minor = Some(bound.minor.unwrap_or(0));
+                let fixed = VersionBound { minor, patch: None, ..*bound };
+                self.matches_ge(bound) && self.matches_eq(&fixed)
+            }
+            VersionRange::Exact(version) => self == version,
+            VersionRange::Ge(bound) => self.matches_ge(bound),
+            VersionRange::Bounded { min, max } => {
+                min.map_or(true, |min| self.matches_ge(&min))
+                    && max.map_or(true, |max| self.matches_lt(&max))
+            }
+        }
+    }
 }
 
 impl FromStr for PackageVersion {
     type Err = EcoString;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (s, prerelease) = match s.split_once('-') {
+            Some((core, pre)) if !core.is_empty() => (core, Some(pre)),
+            _ => (s, None),
+        };
+
         let mut parts = s.split('.');
         let mut next = |kind| {
             let part = parts
@@ -302,7 +1070,70 @@ impl FromStr for PackageVersion {
             Err(eco_format!("version number has unexpected fourth component: `{rest}`"))?;
         }
 
-        Ok(Self { major, minor, patch })
+        if let Some(pre) = prerelease {
+            let valid = |id: &str| {
+                let numeric = id.chars().all(|c| c.is_ascii_digit());
+                !id.is_empty()
+                    && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                    && !(numeric && id.len() > 1 && id.starts_with('0'))
+            };
+            if !pre.split('.').all(valid) {
+                Err(eco_format!("`{pre}` is not a valid pre-release version"))?;
+            }
+        }
+
+        Ok(Self {
+            major,
+            minor,
+            patch,
+            prerelease: prerelease.map(Into::into),
+        })
+    }
+}
+
+impl Ord for PackageVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.prerelease, &other.prerelease) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => cmp_prerelease(a, b),
+            })
+    }
+}
+
+impl PartialOrd for PackageVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compares two pre-release versions identifier by identifier. Numeric
+/// identifiers are compared numerically and are lower than alphanumeric ones.
+/// If all shared identifiers are equal, the one with more identifiers is
+/// greater.
+fn cmp_prerelease(a: &str, b: &str) -> Ordering {
+    let numeric = |id: &str| id.chars().all(|c| c.is_ascii_digit());
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        let (x, y) = match (a.next(), b.next()) {
+            (Some(x), Some(y)) => (x, y),
+            (x, y) => return x.is_some().cmp(&y.is_some()),
+        };
+
+        let ordering = match (numeric(x), numeric(y)) {
+            (true, true) => x.len().cmp(&y.len()).then_with(|| x.cmp(y)),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => x.cmp(y),
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
     }
 }
 
@@ -314,7 +1145,11 @@ impl Debug for PackageVersion {
 
 impl Display for PackageVersion {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.prerelease {
+            write!(f, "-{pre}")?;
+        }
+        Ok(())
     }
 }
 
@@ -401,12 +1236,128 @@ impl<'de> Deserialize<'de> for VersionBound {
     }
 }
 
+/// A range of versions for compatibility specification.
+///
+/// The syntax resembles Cargo's version requirements: `^1.2` allows minor and
+/// patch updates up to the next major version, `~1.2.3` only allows patch
+/// updates, `=1.2.3` requires exactly that version and a bare `1.2` means
+/// `1.2` or newer. Explicit bounds can be given as `>= 0.12, < 1.0`, where
+/// either side may be left out.
+///
+/// Unlike in Cargo, a caret requirement does not treat `0.x` versions
+/// specially, so `^0.13` also allows `0.14.0`. Use `~0.13` to stay within a
+/// minor version.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub enum VersionRange {
+    /// A caret requirement like `^1.2`.
+    Caret(VersionBound),
+    /// A tilde requirement like `~1.2.3`.
+    Tilde(VersionBound),
+    /// An exact requirement like `=1.2.3`.
+    Exact(PackageVersion),
+    /// A minimum requirement like `1.2`.
+    Ge(VersionBound),
+    /// Explicit bounds like `>= 0.12, < 1.0`. The lower bound is inclusive
+    /// and the upper bound is exclusive.
+    Bounded { min: Option<VersionBound>, max: Option<VersionBound> },
+}
+
+impl FromStr for VersionRange {
+    type Err = EcoString;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with(['>', '<']) {
+            let (mut min, mut max) = (None, None);
+            for part in s.split(',').map(str::trim) {
+                if let Some(rest) = part.strip_prefix(">=") {
+                    if min.replace(rest.trim_start().parse()?).is_some() {
+                        Err("version range has multiple lower bounds")?;
+                    }
+                } else if let Some(rest) = part.strip_prefix('<') {
+                    if max.replace(rest.trim_start().parse()?).is_some() {
+                        Err("version range has multiple upper bounds")?;
+                    }
+                } else {
+                    Err(eco_format!(
+                        "`{part}` is not a valid version bound (expected `>=` or `<`)"
+                    ))?;
+                }
+            }
+            Ok(Self::Bounded { min, max })
+        } else if let Some(rest) = s.strip_prefix('^') {
+            rest.trim_start().parse().map(Self::Caret)
+        } else if let Some(rest) = s.strip_prefix('~') {
+            rest.trim_start().parse().map(Self::Tilde)
+        } else if let Some(rest) = s.strip_prefix('=') {
+            rest.trim_start().parse().map(Self::Exact)
+        } else {
+            s.parse().map(Self::Ge)
+        }
+    }
+}
+
+impl Debug for VersionRange {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for VersionRange {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Caret(bound) => write!(f, "^{bound}"),
+            Self::Tilde(bound) => write!(f, "~{bound}"),
+            Self::Exact(version) => write!(f, "={version}"),
+            Self::Ge(bound) => write!(f, "{bound}"),
+            Self::Bounded { min, max } => {
+                if let Some(min) = min {
+                    write!(f, ">= {min}")?;
+                }
+                if min.is_some() && max.is_some() {
+                    f.write_str(", ")?;
+                }
+                if let Some(max) = max {
+                    write!(f, "< {max}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Serialize for VersionRange {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionRange {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let string = EcoString::deserialize(d)?;
+        string.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
     use super::*;
 
+    /// Parses a manifest for `@preview/example:0.1.0` with the given extra
+    /// lines appended to the `[package]` section.
+    fn manifest(extra: &str) -> PackageManifest {
+        let string = format!(
+            "[package]\n\
+             name = \"example\"\n\
+             version = \"0.1.0\"\n\
+             entrypoint = \"lib.typ\"\n\
+             {extra}"
+        );
+        toml::from_str(&string).unwrap()
+    }
+
     #[test]
     fn version_version_match() {
         let v1_1_1 = PackageVersion::from_str("1.1.1").unwrap();
@@ -423,4 +1374,676 @@ mod tests {
         assert!(!v1_1_1.matches_lt(&VersionBound::from_str("1.1").unwrap()));
         assert!(v1_1_1.matches_lt(&VersionBound::from_str("1.2").unwrap()));
     }
+
+    #[test]
+    fn version_from_cargo() {
+        let version = PackageVersion::from_cargo_pkg_version("1.2.3").unwrap();
+        assert_eq!(version.to_string(), "1.2.3");
+
+        let version =
+            PackageVersion::from_cargo_pkg_version("1.2.3-rc.1+build.5").unwrap();
+        assert_eq!(version.to_string(), "1.2.3-rc.1");
+
+        assert!(PackageVersion::from_cargo_pkg_version("1.2").is_err());
+        assert!(PackageVersion::from_cargo_pkg_version("+build").is_err());
+
+        // Within this crate, the macro yields the compiler's version.
+        assert_eq!(package_version!(), PackageVersion::compiler());
+    }
+
+    #[test]
+    fn version_bump() {
+        let bump = |v: &str, f: fn(&PackageVersion) -> Option<PackageVersion>| {
+            f(&PackageVersion::from_str(v).unwrap()).map(|v| v.to_string())
+        };
+
+        assert_eq!(bump("0.0.1", PackageVersion::bump_major), Some("1.0.0".into()));
+        assert_eq!(bump("0.0.1", PackageVersion::bump_minor), Some("0.1.0".into()));
+        assert_eq!(bump("0.0.1", PackageVersion::bump_patch), Some("0.0.2".into()));
+        assert_eq!(bump("0.0.1", PackageVersion::next_breaking), Some("0.1.0".into()));
+
+        assert_eq!(bump("0.1.0", PackageVersion::bump_major), Some("1.0.0".into()));
+        assert_eq!(bump("0.1.0", PackageVersion::bump_minor), Some("0.2.0".into()));
+        assert_eq!(bump("0.1.0", PackageVersion::bump_patch), Some("0.1.1".into()));
+        assert_eq!(bump("0.1.0", PackageVersion::next_breaking), Some("0.2.0".into()));
+
+        assert_eq!(bump("1.0.0", PackageVersion::bump_major), Some("2.0.0".into()));
+        assert_eq!(bump("1.0.0", PackageVersion::bump_minor), Some("1.1.0".into()));
+        assert_eq!(bump("1.0.0", PackageVersion::bump_patch), Some("1.0.1".into()));
+        assert_eq!(bump("1.0.0", PackageVersion::next_breaking), Some("2.0.0".into()));
+
+        assert_eq!(bump("1.2.3-rc.1", PackageVersion::bump_patch), Some("1.2.3".into()));
+        assert_eq!(bump("1.2.3-rc.1", PackageVersion::bump_minor), Some("1.3.0".into()));
+        assert_eq!(bump("1.3.0-rc.1", PackageVersion::bump_minor), Some("1.3.0".into()));
+        assert_eq!(bump("1.3.0-rc.1", PackageVersion::bump_major), Some("2.0.0".into()));
+        assert_eq!(bump("2.0.0-rc.1", PackageVersion::bump_major), Some("2.0.0".into()));
+        assert_eq!(
+            bump("2.0.0-rc.1", PackageVersion::next_breaking),
+            Some("2.0.0".into())
+        );
+        assert_eq!(
+            bump("0.2.0-rc.1", PackageVersion::next_breaking),
+            Some("0.2.0".into())
+        );
+
+        let max = u32::MAX;
+        assert_eq!(bump(&format!("{max}.0.0"), PackageVersion::bump_major), None);
+        assert_eq!(bump(&format!("{max}.0.0"), PackageVersion::next_breaking), None);
+        assert_eq!(bump(&format!("0.{max}.0"), PackageVersion::next_breaking), None);
+        assert_eq!(bump(&format!("1.2.{max}"), PackageVersion::bump_patch), None);
+        assert_eq!(
+            bump(&format!("1.2.{max}"), PackageVersion::bump_minor),
+            Some("1.3.0".into())
+        );
+    }
+
+    #[test]
+    fn version_prerelease_parse() {
+        let v = PackageVersion::from_str("1.0.0-alpha.1").unwrap();
+        assert_eq!((v.major, v.minor, v.patch), (1, 0, 0));
+        assert_eq!(v.prerelease.as_deref(), Some("alpha.1"));
+        assert_eq!(v.to_string(), "1.0.0-alpha.1");
+
+        let v = PackageVersion::from_str("1.0.0-x-y.7").unwrap();
+        assert_eq!(v.prerelease.as_deref(), Some("x-y.7"));
+
+        assert!(PackageVersion::from_str("1.0.0-").is_err());
+        assert!(PackageVersion::from_str("1.0.0-alpha..1").is_err());
+        assert!(PackageVersion::from_str("1.0.0-alpha.01").is_err());
+        assert!(PackageVersion::from_str("1.0.0-alpha_1").is_err());
+        assert!(PackageVersion::from_str("1.0-alpha").is_err());
+        assert_eq!(
+            PackageVersion::from_str("-3.0.0"),
+            Err("`-3` is not a valid major version".into())
+        );
+    }
+
+    #[test]
+    fn version_prerelease_ordering() {
+        let versions = [
+            "0.9.9",
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1-0",
+            "1.0.1",
+        ]
+        .map(|v| PackageVersion::from_str(v).unwrap());
+
+        for (i, a) in versions.iter().enumerate() {
+            for (j, b) in versions.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "comparing {a} and {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn version_prefer_releases() {
+        let latest = |versions: &[&str]| {
+            versions
+                .iter()
+                .map(|v| PackageVersion::from_str(v).unwrap())
+                .max_by(PackageVersion::cmp_preferring_releases)
+                .map(|v| v.to_string())
+        };
+
+        assert_eq!(latest(&["0.9.0", "1.0.0-rc.1"]), Some("0.9.0".into()));
+        assert_eq!(latest(&["0.9.0", "1.0.0-rc.1", "1.0.0"]), Some("1.0.0".into()));
+        assert_eq!(latest(&["1.0.0-rc.1", "1.0.0-rc.2"]), Some("1.0.0-rc.2".into()));
+        assert_eq!(latest(&[]), None);
+    }
+
+    #[test]
+    fn version_prerelease_match() {
+        let v1_0_0_rc = PackageVersion::from_str("1.0.0-rc.1").unwrap();
+
+        for bound in ["1", "1.0", "1.0.0"] {
+            let bound = VersionBound::from_str(bound).unwrap();
+            assert!(!v1_0_0_rc.matches_eq(&bound));
+            assert!(v1_0_0_rc.matches_lt(&bound));
+            assert!(!v1_0_0_rc.matches_gt(&bound));
+            assert!(!v1_0_0_rc.matches_ge(&bound));
+            assert!(v1_0_0_rc.matches_le(&bound));
+        }
+        assert!(v1_0_0_rc.matches_ge(&VersionBound::from_str("0.9").unwrap()));
+
+        let v1_5_0_rc = PackageVersion::from_str("1.5.0-rc.1").unwrap();
+        assert!(v1_5_0_rc.matches_eq(&VersionBound::from_str("1").unwrap()));
+        assert!(!v1_5_0_rc.matches_lt(&VersionBound::from_str("1").unwrap()));
+        assert!(v1_5_0_rc.matches_lt(&VersionBound::from_str("1.5").unwrap()));
+
+        // Bounds that mean the same thing must give the same answer.
+        let satisfies = |v: &str, r: &str| {
+            PackageVersion::from_str(v)
+                .unwrap()
+                .satisfies(&VersionRange::from_str(r).unwrap())
+        };
+        for v in ["1.0.0-rc.1", "1.0.0", "1.0.1-rc.1", "1.1.0-rc.1", "2.0.0-rc.1"] {
+            for (short, full) in [
+                ("^1.0", "^1.0.0"),
+                ("~1.0", "~1.0.0"),
+                ("1.0", "1.0.0"),
+                (">= 1.0", ">= 1.0.0"),
+                ("< 1.0", "< 1.0.0"),
+                ("< 2.0", "< 2.0.0"),
+            ] {
+                assert_eq!(satisfies(v, short), satisfies(v, full), "{v} {short}");
+            }
+        }
+        assert!(!satisfies("1.0.0-rc.1", "^1.0"));
+        assert!(satisfies("1.1.0-rc.1", "^1.0"));
+        assert!(satisfies("1.0.0-rc.1", "< 1.0"));
+        assert!(!satisfies("2.0.0-rc.1", "^1.0"));
+    }
+
+    #[test]
+    fn version_range_parse() {
+        let range = |s| VersionRange::from_str(s).unwrap();
+        let bound = |s| VersionBound::from_str(s).unwrap();
+        let version = |s| PackageVersion::from_str(s).unwrap();
+
+        assert_eq!(range("^1.2"), VersionRange::Caret(bound("1.2")));
+        assert_eq!(range("~1.2.3"), VersionRange::Tilde(bound("1.2.3")));
+        assert_eq!(range("=1.2.3"), VersionRange::Exact(version("1.2.3")));
+        assert_eq!(range("1.2"), VersionRange::Ge(bound("1.2")));
+
+        for s in ["^0.13", "~1.2.3", "=1.2.3", "1.2"] {
+            assert_eq!(range(s).to_string(), s);
+        }
+
+        assert!(VersionRange::from_str("^").is_err());
+        assert!(VersionRange::from_str("=1.2").is_err());
+        assert!(VersionRange::from_str("~x").is_err());
+    }
+
+    #[test]
+    fn version_range_bounded() {
+        let range = |s: &str| VersionRange::from_str(s).unwrap();
+        let bound = |s| Some(VersionBound::from_str(s).unwrap());
+        let satisfies =
+            |v: &str, r: &str| PackageVersion::from_str(v).unwrap().satisfies(&range(r));
+
+        assert_eq!(
+            range(">= 0.12, < 1.0"),
+            VersionRange::Bounded { min: bound("0.12"), max: bound("1.0") }
+        );
+        assert_eq!(
+            range(">=0.12"),
+            VersionRange::Bounded { min: bound("0.12"), max: None }
+        );
+        assert_eq!(range("< 1"), VersionRange::Bounded { min: None, max: bound("1") });
+        for s in [">= 0.12, < 1.0", ">= 0.12", "< 1"] {
+            assert_eq!(range(s).to_string(), s);
+        }
+
+        assert!(satisfies("0.12.0", ">= 0.12, < 1.0"));
+        assert!(satisfies("0.99.1", ">= 0.12, < 1.0"));
+        assert!(!satisfies("0.11.1", ">= 0.12, < 1.0"));
+        assert!(!satisfies("1.0.0", ">= 0.12, < 1.0"));
+        assert!(satisfies("5.0.0", ">= 0.12"));
+        assert!(!satisfies("0.11.0", ">= 0.12"));
+        assert!(satisfies("0.1.0", "< 1"));
+        assert!(!satisfies("1.2.0", "< 1"));
+
+        assert!(VersionRange::from_str(">= 0.12, >= 0.13").is_err());
+        assert!(VersionRange::from_str("< 1, < 2").is_err());
+        assert!(VersionRange::from_str(">= 0.12, 1.0").is_err());
+        assert!(VersionRange::from_str("> 0.12").is_err());
+    }
+
+    #[test]
+    fn manifest_compiler_bounds() {
+        let spec = PackageSpec::from_str("@preview/example:0.1.0").unwrap();
+        let with_compiler =
+            |compiler: &str| manifest(&format!("compiler = \"{compiler}\""));
+
+        let both = with_compiler(">= 0.1, < 99.0");
+        assert_eq!(both.package.compiler_max(), VersionBound::from_str("99.0").ok());
+        assert!(both.validate(&spec).is_ok());
+        assert_eq!(
+            toml::to_string(&both).unwrap(),
+            toml::to_string(&with_compiler(">=0.1,<99.0")).unwrap(),
+        );
+        assert!(toml::to_string(&both).unwrap().contains(">= 0.1, < 99.0"));
+
+        let lower = with_compiler(">= 99.0");
+        assert_eq!(lower.package.compiler_max(), None);
+        assert!(lower.validate(&spec).is_err());
+
+        let upper = with_compiler("< 0.1");
+        assert_eq!(upper.package.compiler_max(), VersionBound::from_str("0.1").ok());
+        assert!(upper.validate(&spec).is_err());
+        assert!(with_compiler("< 99").validate(&spec).is_ok());
+    }
+
+    #[test]
+    fn version_range_match() {
+        let satisfies = |v: &str, r: &str| {
+            PackageVersion::from_str(v)
+                .unwrap()
+                .satisfies(&VersionRange::from_str(r).unwrap())
+        };
+
+        assert!(satisfies("1.2.0", "^1.2"));
+        assert!(satisfies("1.9.3", "^1.2"));
+        assert!(!satisfies("2.0.0", "^1.2"));
+        assert!(!satisfies("1.1.9", "^1.2"));
+        assert!(satisfies("0.13.4", "^0.13"));
+        assert!(satisfies("0.14.0", "^0.13"));
+        assert!(!satisfies("0.12.9", "^0.13"));
+        assert!(!satisfies("1.0.0", "^0.13"));
+        assert!(satisfies("0.0.4", "^0.0.3"));
+        assert!(satisfies("0.9.0", "^0"));
+
+        assert!(satisfies("1.2.3", "~1.2.3"));
+        assert!(satisfies("1.2.9", "~1.2.3"));
+        assert!(!satisfies("1.3.0", "~1.2.3"));
+        assert!(!satisfies("1.2.2", "~1.2.3"));
+        assert!(satisfies("1.0.5", "~1"));
+        assert!(!satisfies("1.9.0", "~1"));
+        assert!(satisfies("0.13.2", "~0.13"));
+        assert!(!satisfies("0.14.0", "~0.13"));
+
+        assert!(satisfies("1.2.3", "=1.2.3"));
+        assert!(!satisfies("1.2.4", "=1.2.3"));
+
+        assert!(satisfies("1.2.0", "1.2"));
+        assert!(satisfies("3.0.0", "1.2"));
+        assert!(!satisfies("1.1.0", "1.2"));
+    }
+
+    #[test]
+    fn versionless_spec() {
+        let spec = PackageSpec::from_str("@preview/example:0.1.0").unwrap();
+        let versionless = VersionlessPackageSpec::from(spec.clone());
+        assert_eq!(versionless, spec.versionless());
+        assert_eq!(versionless.to_string(), "@preview/example");
+        assert_eq!(versionless.clone().at(spec.version.clone()), spec);
+
+        let bare = VersionlessPackageSpec::from_bare("preview", "example").unwrap();
+        assert_eq!(bare, versionless);
+        assert!(VersionlessPackageSpec::from_bare("@preview", "example").is_err());
+        assert!(VersionlessPackageSpec::from_bare("preview", "").is_err());
+
+        let mut map = std::collections::HashMap::new();
+        map.insert(versionless, 1);
+        assert_eq!(map.get(&bare), Some(&1));
+    }
+
+    #[test]
+    fn spec_url_encoding() {
+        let spec = PackageSpec::from_str("@preview/example-pkg:1.0.0-rc.1").unwrap();
+        let encoded = spec.to_url_encoded();
+        assert_eq!(encoded, "%40preview%2Fexample-pkg%3A1.0.0-rc.1");
+        assert_eq!(PackageSpec::from_url_encoded(&encoded), Ok(spec.clone()));
+
+        assert_eq!(
+            PackageSpec::from_url_encoded("%40preview%2fexample-pkg:1.0.0-rc.1"),
+            Ok(spec.clone())
+        );
+        assert_eq!(
+            PackageSpec::from_url_encoded("@preview/example-pkg:1.0.0-rc.1"),
+            Ok(spec)
+        );
+
+        let spec = PackageSpec::from_str("@preview/bänder:0.1.0").unwrap();
+        let encoded = spec.to_url_encoded();
+        assert_eq!(encoded, "%40preview%2Fb%C3%A4nder%3A0.1.0");
+        assert_eq!(PackageSpec::from_url_encoded(&encoded), Ok(spec));
+
+        assert!(PackageSpec::from_url_encoded("%40preview%2Fexample%3").is_err());
+        assert!(PackageSpec::from_url_encoded("%4Gpreview/example:0.1.0").is_err());
+        assert!(PackageSpec::from_url_encoded("%+4preview/example:0.1.0").is_err());
+        assert!(PackageSpec::from_url_encoded("%FFpreview/example:0.1.0").is_err());
+        assert!(PackageSpec::from_url_encoded("preview%2Fexample%3A0.1.0").is_err());
+    }
+
+    #[test]
+    fn spec_ordering() {
+        let mut specs = [
+            "@preview/cetz:0.2.0",
+            "@local/mine:1.0.0",
+            "@preview/cetz:0.2.2",
+            "@preview/algo:0.3.3",
+            "@alpha/zed:0.1.0",
+            "@preview/cetz:0.10.0",
+            "@local/mine:1.0.0-rc.1",
+            "@alpha/abc:2.0.0",
+        ]
+        .map(|s| PackageSpec::from_str(s).unwrap());
+
+        sort_specs(&mut specs);
+        assert_eq!(
+            specs.map(|spec| spec.to_string()),
+            [
+                "@alpha/abc:2.0.0",
+                "@alpha/zed:0.1.0",
+                "@local/mine:1.0.0",
+                "@local/mine:1.0.0-rc.1",
+                "@preview/algo:0.3.3",
+                "@preview/cetz:0.10.0",
+                "@preview/cetz:0.2.2",
+                "@preview/cetz:0.2.0",
+            ]
+        );
+
+        let a = VersionlessPackageSpec::from_str("@local/b").unwrap();
+        let b = VersionlessPackageSpec::from_str("@preview/a").unwrap();
+        assert!(a < b);
+    }
+
+    #[test]
+    fn manifest_urls() {
+        let manifest: PackageManifest = toml::from_str(
+            r#"
+            [package]
+            name = "example"
+            version = "0.1.0"
+            entrypoint = "lib.typ"
+            repository = "https://github.com/typst/example"
+            homepage = "docs/index.html"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.package.repository_host(), Some("github.com"));
+        assert_eq!(
+            manifest.validate(&PackageSpec::from_str("@preview/example:0.1.0").unwrap()),
+            Ok(vec![ManifestWarning::InvalidUrl("homepage", "docs/index.html".into())])
+        );
+
+        let string = toml::to_string(&manifest).unwrap();
+        assert!(string.contains("repository = \"https://github.com/typst/example\""));
+        assert_eq!(toml::from_str::<PackageManifest>(&string).unwrap(), manifest);
+    }
+
+    #[test]
+    fn manifest_description() {
+        let spec = PackageSpec::from_str("@preview/example:0.1.0").unwrap();
+        let validate = |extra: &str| manifest(extra).validate(&spec);
+
+        assert_eq!(validate("description = \"Draw diagrams.\""), Ok(vec![]));
+        assert_eq!(
+            validate("description = \"Draw\\ndiagrams.\""),
+            Ok(vec![ManifestWarning::MultilineDescription])
+        );
+
+        let limit = "a".repeat(MAX_DESCRIPTION_LEN);
+        assert_eq!(validate(&format!("description = \"{limit}\"")), Ok(vec![]));
+        assert_eq!(
+            validate(&format!("description = \"{limit}b\"")),
+            Ok(vec![ManifestWarning::DescriptionTooLong(MAX_DESCRIPTION_LEN + 1)])
+        );
+
+        let template = "[template]\npath = \"template\"\nentrypoint = \"main.typ\"";
+        assert_eq!(validate(template), Ok(vec![ManifestWarning::MissingDescription]));
+        assert_eq!(
+            validate(&format!("description = \"Write a thesis.\"\n{template}")),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn short_description() {
+        let mut info = manifest("").package;
+        assert_eq!(info.short_description(10), "");
+
+        info.description = Some("Draw beautiful diagrams with ease.".into());
+        assert_eq!(info.short_description(100), "Draw beautiful diagrams with ease.");
+        assert_eq!(info.short_description(34), "Draw beautiful diagrams with ease.");
+        assert_eq!(info.short_description(33), "Draw beautiful diagrams with…");
+        assert_eq!(info.short_description(24), "Draw beautiful diagrams…");
+        assert_eq!(info.short_description(16), "Draw beautiful…");
+        assert_eq!(info.short_description(5), "Draw…");
+        assert_eq!(info.short_description(3), "Dr…");
+        assert_eq!(info.short_description(1), "…");
+        assert_eq!(info.short_description(0), "");
+    }
+
+    #[test]
+    fn manifest_metadata() {
+        let manifest: PackageManifest = toml::from_str(
+            r#"
+            [package]
+            name = "example"
+            version = "0.1.0"
+            entrypoint = "lib.typ"
+
+            [package.metadata.linter]
+            strict = true
+            ignore = ["tests/**"]
+
+            [package.metadata.docs]
+            theme = { accent = "teal" }
+            "#,
+        )
+        .unwrap();
+
+        let linter = manifest.package.metadata_for_tool("linter").unwrap();
+        assert_eq!(linter.get("strict"), Some(&toml::Value::Boolean(true)));
+        assert_eq!(
+            manifest.package.metadata_for_tool("docs").unwrap()["theme"]["accent"]
+                .as_str(),
+            Some("teal")
+        );
+        assert_eq!(manifest.package.metadata_for_tool("other"), None);
+
+        let string = toml::to_string(&manifest).unwrap();
+        assert_eq!(toml::from_str::<PackageManifest>(&string).unwrap(), manifest);
+
+        let plain = manifest("");
+        assert_eq!(plain.package.metadata, None);
+        assert_eq!(plain.package.metadata_for_tool("linter"), None);
+    }
+
+    #[test]
+    fn manifest_deprecation() {
+        let spec = PackageSpec::from_str("@preview/example:0.1.0").unwrap();
+        let validate = |extra: &str| manifest(extra).validate(&spec).unwrap();
+
+        assert_eq!(validate(""), vec![]);
+        assert_eq!(validate("deprecated = false"), vec![]);
+
+        let warnings = validate("deprecated = true");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].to_string(),
+            "package @preview/example:0.1.0 is deprecated"
+        );
+        assert!(warnings[0].is_user_facing());
+        assert!(!ManifestWarning::MissingDescription.is_user_facing());
+
+        let warnings = validate(
+            "deprecated = true\n\
+             deprecation_message = \"use @preview/other instead\"",
+        );
+        assert_eq!(
+            warnings,
+            vec![ManifestWarning::Deprecated(DeprecationWarning {
+                spec: spec.clone(),
+                message: Some("use @preview/other instead".into()),
+            })]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "package @preview/example:0.1.0 is deprecated: use @preview/other instead"
+        );
+    }
+
+    #[test]
+    fn manifest_keywords_and_categories() {
+        let spec = PackageSpec::from_str("@preview/example:0.1.0").unwrap();
+        let validate = |extra: &str| manifest(extra).validate(&spec);
+
+        assert_eq!(validate(""), Ok(vec![]));
+        assert_eq!(validate("keywords = [\"diagram\", \"graph\"]"), Ok(vec![]));
+        assert_eq!(validate("categories = [\"visualization\", \"fun\"]"), Ok(vec![]));
+        assert_eq!(
+            validate("keywords = [\"a\", \"b\", \"c\", \"d\", \"e\", \"f\"]"),
+            Ok(vec![ManifestWarning::TooManyKeywords(6)])
+        );
+        assert_eq!(
+            validate("keywords = [\"a-keyword-that-is-too-long\"]"),
+            Ok(vec![ManifestWarning::KeywordTooLong(
+                "a-keyword-that-is-too-long".into()
+            )])
+        );
+        assert_eq!(
+            validate("categories = [\"misc\", \"fun\"]"),
+            Ok(vec![ManifestWarning::UnknownCategory("misc".into())])
+        );
+    }
+
+    #[test]
+    fn include_exclude() {
+        let info = |include: &[&str], exclude: &[&str]| {
+            let mut info = manifest("").package;
+            info.include = include.iter().map(|&s| s.into()).collect();
+            info.exclude = exclude.iter().map(|&s| s.into()).collect();
+            info
+        };
+
+        let all = info(&[], &[]);
+        assert!(all.should_include("lib.typ"));
+        assert!(all.should_include("tests/test.typ"));
+
+        let typ = info(&["*.typ", "!tests/**"], &[]);
+        assert!(typ.should_include("lib.typ"));
+        assert!(typ.should_include("./src/utils.typ"));
+        assert!(!typ.should_include("README.md"));
+        assert!(!typ.should_include("tests/test.typ"));
+        assert!(!typ.should_include("tests/deep/test.typ"));
+
+        let excluded = info(&[], &["tests", "docs/**/*.pdf", "!docs/manual.pdf"]);
+        assert!(excluded.should_include("lib.typ"));
+        assert!(!excluded.should_include("tests/test.typ"));
+        assert!(!excluded.should_include("src/tests/test.typ"));
+        assert!(!excluded.should_include("docs/img/figure.pdf"));
+        assert!(excluded.should_include("docs/manual.pdf"));
+        assert!(excluded.should_include("docs/manual.typ"));
+
+        let overridden = info(&["lib.typ"], &["lib.typ"]);
+        assert!(overridden.should_include("lib.typ"));
+    }
+
+    #[test]
+    fn glob_matching() {
+        assert!(match_glob("*.typ", "lib.typ"));
+        assert!(!match_glob("*.typ", "src/lib.typ"));
+        assert!(match_glob("**/*.typ", "lib.typ"));
+        assert!(match_glob("**/*.typ", "src/deep/lib.typ"));
+        assert!(match_glob("src/**", "src/deep/lib.typ"));
+        assert!(match_glob("l?b.typ", "lib.typ"));
+        assert!(!match_glob("l?b.typ", "l/b.typ"));
+        assert!(match_glob("bänder.*", "bänder.typ"));
+    }
+
+    #[test]
+    fn category_validation() {
+        assert!(validate_category("layout"));
+        assert!(validate_category("cv"));
+        assert!(!validate_category("Layout"));
+        assert!(!validate_category(""));
+    }
+
+    #[test]
+    fn url_parts() {
+        assert_eq!(url_host("https://github.com/typst/typst"), Some("github.com"));
+        assert_eq!(url_host("https://gitlab.com"), Some("gitlab.com"));
+        assert_eq!(url_host("ssh://git@codeberg.org:22/x"), Some("codeberg.org"));
+        assert_eq!(url_host("mailto:jane@example.com"), None);
+        assert_eq!(url_host("github.com/typst/typst"), None);
+        assert!(is_absolute_url("https://typst.app"));
+        assert!(!is_absolute_url("/docs"));
+        assert!(!is_absolute_url("1http://x"));
+    }
+
+    #[test]
+    fn author_parse() {
+        let author = Author::from_str("Jane Doe <jane@example.com>").unwrap();
+        assert_eq!(author.name, "Jane Doe");
+        assert_eq!(author.contact.as_deref(), Some("jane@example.com"));
+        assert_eq!(author.email(), Some("jane@example.com"));
+        assert_eq!(author.to_string(), "Jane Doe <jane@example.com>");
+        assert!(author.is_valid());
+
+        let author = Author::from_str("John Doe").unwrap();
+        assert_eq!(author.name, "John Doe");
+        assert_eq!(author.contact, None);
+        assert!(author.is_valid());
+
+        for s in ["Jane <@jane-doe>", "Jane <https://jane.example.com>"] {
+            let author = Author::from_str(s).unwrap();
+            assert_eq!(author.to_string(), s);
+            assert_eq!(author.email(), None);
+            assert!(author.is_valid());
+        }
+
+        for s in ["", "Jane <jane>", "Jane <@>", "Jane <jane@example.>"] {
+            assert!(!Author::from_str(s).unwrap().is_valid());
+        }
+    }
+
+    #[test]
+    fn manifest_authors() {
+        let manifest: PackageManifest = toml::from_str(
+            r#"
+            [package]
+            name = "example"
+            version = "0.1.0"
+            entrypoint = "lib.typ"
+            authors = [
+                "Jane Doe <@jane-doe>",
+                { name = "John Doe", email = "john@example.com" },
+                "Max <not a contact>",
+            ]
+            "#,
+        )
+        .unwrap();
+
+        let authors = &manifest.package.authors;
+        assert_eq!(authors[1].email(), Some("john@example.com"));
+        assert_eq!(
+            manifest.validate(&PackageSpec::from_str("@preview/example:0.1.0").unwrap()),
+            Ok(vec![ManifestWarning::InvalidAuthor(authors[2].clone())])
+        );
+    }
+
+    #[test]
+    fn license_spdx() {
+        assert!(is_spdx_expression("Apache-2.0"));
+        assert!(is_spdx_expression("MIT OR Apache-2.0"));
+        assert!(is_spdx_expression("(MIT AND BSD-3-Clause) OR GPL-2.0+"));
+        assert!(is_spdx_expression("GPL-2.0-or-later WITH Classpath-exception-2.0"));
+        assert!(is_spdx_expression("LicenseRef-Custom"));
+
+        assert!(!is_spdx_expression(""));
+        assert!(!is_spdx_expression("MIT OR"));
+        assert!(!is_spdx_expression("MIT ORApache-2.0"));
+        assert!(!is_spdx_expression("MIT / Apache-2.0"));
+        assert!(!is_spdx_expression("(MIT OR Apache-2.0"));
+        assert!(!is_spdx_expression("MIT WITH"));
+    }
+
+    #[test]
+    fn manifest_license() {
+        let spec = PackageSpec::from_str("@preview/example:0.1.0").unwrap();
+        let validate = |extra: &str| manifest(extra).validate(&spec);
+
+        assert_eq!(validate("license = \"MIT OR Apache-2.0\""), Ok(vec![]));
+        let warnings = validate("license = \"MIT / Apache-2.0\"").unwrap();
+        assert_eq!(
+            warnings,
+            vec![ManifestWarning::InvalidLicense("MIT / Apache-2.0".into())]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "package manifest contains invalid SPDX license expression `MIT / Apache-2.0`"
+        );
+    }
 }
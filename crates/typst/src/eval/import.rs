@@ -177,7 +177,11 @@ fn import_package(vm: &mut Vm, spec: PackageSpec, span: Span) -> SourceResult<Mo
     let manifest: PackageManifest = toml::from_str(string)
         .map_err(|err| eco_format!("package manifest is malformed ({})", err.message()))
         .at(span)?;
-    manifest.validate(&spec).at(span)?;
+    for warning in manifest.validate(&spec).at(span)? {
+        if warning.is_user_facing() {
+            vm.engine.sink.warn(warning!(span, "{warning}"));
+        }
+    }
 
     // Evaluate the entry point.
     let entrypoint_id = manifest_id.join(&manifest.package.entrypoint);
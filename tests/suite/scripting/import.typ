@@ -326,6 +326,12 @@ This is never reached.
 // Error: 9-29 package requires typst 1.0.0 or newer (current version is VERSION)
 #import "@test/future:0.1.0": future
 
+--- import-from-package-deprecated ---
+// Test importing a deprecated package.
+// Warning: 9-26 package @test/old:0.1.0 is deprecated: use @test/adder instead
+#import "@test/old:0.1.0": add
+#test(add(2, 8), 10)
+
 --- import-from-package-namespace-invalid-1 ---
 // Error: 9-13 `@` is not a valid package namespace
 #import "@@": *